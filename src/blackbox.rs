@@ -0,0 +1,55 @@
+//! An optimization barrier for benchmarked code.
+
+/// Pass a value through this function to keep the optimizer from hoisting,
+/// constant-folding or eliminating the computation that produced it. Use it
+/// on the return value of a benchmarked closure (the benchmarking functions
+/// in this crate do this for you), or on any input the optimizer might
+/// otherwise see through and fold away.
+///
+/// On `x86`/`x86_64` this is implemented with an inline assembly barrier
+/// that tells the optimizer the value may have been observed or mutated
+/// elsewhere; on other targets it falls back to a volatile read, which the
+/// optimizer is not allowed to elide. This mirrors the approach taken by
+/// the standard test harness's own `black_box` before it was stabilized.
+/// ## Example
+/// ```
+/// use devtimer::black_box;
+///
+/// let mut v = Vec::with_capacity(4);
+/// for i in 0..4 {
+///     // Pin `i` so the loop that fills `v` isn't optimized away just
+///     // because `v` itself goes unused afterwards.
+///     v.push(black_box(i));
+/// }
+/// ```
+#[inline(always)]
+pub fn black_box<T>(dummy: T) -> T {
+    imp::black_box(dummy)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod imp {
+    /// Inline assembly barrier: tells the optimizer the value may have been
+    /// observed or mutated elsewhere.
+    #[inline(always)]
+    pub(super) fn black_box<T>(mut dummy: T) -> T {
+        unsafe {
+            std::arch::asm!("/* {0} */", inout(reg) &mut dummy => _, options(nostack, preserves_flags));
+        }
+        dummy
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+mod imp {
+    /// Volatile-read fallback for targets without an inline assembly
+    /// barrier above; the optimizer is not allowed to elide a volatile read.
+    #[inline(always)]
+    pub(super) fn black_box<T>(dummy: T) -> T {
+        unsafe {
+            let ret = std::ptr::read_volatile(&dummy);
+            std::mem::forget(dummy);
+            ret
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{simple::SimpleTimer, traits::TimeDifferenceExt};
+use std::collections::HashMap;
+
+/// # Complex Timer
+/// A complex timer wraps around a map of timer names and their corresponding
+/// [`SimpleTimer`] instances.
+pub struct ComplexTimer {
+    /// Map of timers and the corresponding `SimpleTimer`
+    timers: HashMap<&'static str, SimpleTimer>,
+}
+
+impl Default for ComplexTimer {
+    fn default() -> Self {
+        ComplexTimer::new()
+    }
+}
+
+impl ComplexTimer {
+    /// Return a new `ComplexTimer` instance
+    pub fn new() -> Self {
+        ComplexTimer {
+            timers: HashMap::new(),
+        }
+    }
+    /// Create a new timer tag. If the timer tag already exists, then this
+    /// function returns an error.
+    pub fn create_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
+        if self.timers.contains_key(timer_name) {
+            Err("This timer already exists")
+        } else {
+            let _ = self
+                .timers
+                .insert(timer_name, SimpleTimer::new_named(timer_name.to_owned()));
+            Ok(())
+        }
+    }
+    /// Start a timer with tag `timer_name`. Returns an error if this timer tag doesn't
+    /// exist, or if it has already been started (whether it is still running, paused
+    /// or already stopped)
+    pub fn start_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
+        match self.timers.get_mut(timer_name) {
+            None => Err("This timer does not exist"),
+            Some(x) => {
+                if x.start_checked() {
+                    Ok(())
+                } else {
+                    Err("This timer is already running")
+                }
+            }
+        }
+    }
+    /// Stop a timer with tag `timer_name`. Returns an error if this timer tag doesn't
+    /// exist, or if it was already stopped or never started
+    pub fn stop_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
+        match self.timers.get_mut(timer_name) {
+            None => Err("This timer does not exist"),
+            Some(x) => {
+                if x.stop_checked() {
+                    Ok(())
+                } else {
+                    Err("This timer was already stopped, or was never started")
+                }
+            }
+        }
+    }
+    /// Get the time in seconds for a timer with tag `timer_name`
+    pub fn time_in_secs(&self, timer_name: &'static str) -> Option<u64> {
+        self.timers.get(timer_name).and_then(|t| t.time_in_secs())
+    }
+
+    /// Get the time in milliseconds for a timer with tag `timer_name`
+    pub fn time_in_millis(&self, timer_name: &'static str) -> Option<u128> {
+        self.timers
+            .get(timer_name)
+            .and_then(|t| t.time_in_millis())
+    }
+
+    /// Get the time in microseconds for a timer with tag `timer_name`
+    pub fn time_in_micros(&self, timer_name: &'static str) -> Option<u128> {
+        self.timers
+            .get(timer_name)
+            .and_then(|t| t.time_in_micros())
+    }
+
+    /// Get the time in nanoseconds for a timer with tag `timer_name`
+    pub fn time_in_nanos(&self, timer_name: &'static str) -> Option<u128> {
+        self.timers.get(timer_name).and_then(|t| t.time_in_nanos())
+    }
+    /// Delete a timer with tag `timer_name`
+    pub fn delete_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
+        match self.timers.remove_entry(timer_name) {
+            Some(_) => Ok(()),
+            None => Err("This timer does not exist"),
+        }
+    }
+
+    /// Delete all set timers
+    pub fn clear_timers(&mut self) {
+        self.timers.clear();
+    }
+
+    /// Print all results in the following format:
+    ///
+    /// ```log
+    /// timerx - 120 ns
+    /// timery - 1233 ns
+    /// ...
+    /// ```
+    pub fn print_results(&self) {
+        println!();
+        for (k, v) in self.timers.iter() {
+            println!("{} - {} ns", k, v.time_in_nanos().unwrap());
+        }
+    }
+    /// Returns an iterator of timer tags and the corresponding `SimpleTimer` instances
+    /// # Example
+    /// ```
+    /// use devtimer::{DevTime, TimeDifferenceExt};
+    /// fn main() {
+    ///     let mut dt = DevTime::new_complex();
+    ///     for (name, timer) in dt.iter() {
+    ///         println!("Timer: {} took {} ns", name, timer.time_in_nanos().unwrap());
+    ///     }
+    /// }
+    /// ```
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, &'static str, SimpleTimer> {
+        self.timers.iter()
+    }
+}
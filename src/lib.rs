@@ -9,7 +9,7 @@
 //! ## Examples: `DevTime::new_simple()`
 //!
 //! ```
-//! use devtimer::DevTime;
+//! use devtimer::{DevTime, TimeDifferenceExt};
 //! fn main() {
 //!     let mut devtime = DevTime::new_simple();
 //!     devtime.start();
@@ -37,7 +37,7 @@
 //! ```
 //! ## Examples: `DevTime::new_complex()`
 //! ```
-//! use devtimer::DevTime;
+//! use devtimer::{DevTime, TimeDifferenceExt};
 //! let mut dt = DevTime::new_complex();
 //!
 //! // Create a new timer tag `pk12`
@@ -66,17 +66,50 @@
 //! dt.clear_timers();
 //! ```
 //!
-use std::collections::HashMap;
-use std::time;
+use std::time::{Duration, Instant};
+
+mod blackbox;
+mod complex;
+mod report;
+mod runner;
+mod simple;
+mod traits;
+
+pub use blackbox::black_box;
+pub use complex::ComplexTimer;
+pub use report::RunThroughReport;
+pub use runner::{TimerHandle, TimerRunner};
+pub use simple::SimpleTimer;
+pub use traits::TimeDifferenceExt;
+
 /// The `DevTime` struct provides a simple implementation
 /// for benchmarking operations using the standard library.
 pub struct DevTime {}
 
+/// Time a closure and return how long it took alongside its return value.
+/// A free-function shorthand for [`SimpleTimer::span`], for the common case
+/// of "how long did this block take" where a full [`DevTime`] isn't needed.
+/// ## Example
+/// ```
+/// use devtimer::span;
+///
+/// let (elapsed, doubled) = span(|| 21 * 2);
+/// assert_eq!(doubled, 42);
+/// println!("took {:?}", elapsed);
+/// ```
+pub fn span<R>(f: impl FnOnce() -> R) -> (Duration, R) {
+    SimpleTimer::span(f)
+}
+
 /// The bench struct provides the `benchmark` function that can be used
 /// for benchmarking operations using the `bench()` member function
 /// Benchmark an operation by running multiple iterations.
 /// This function returns a `RunThroughReport` object which can be used to get
 /// the benchmark results.
+///
+/// The closure's return value is passed through [`black_box`] on every
+/// iteration, so the optimizer cannot hoist or delete a fast closure body
+/// just because its result is otherwise unused.
 /// ## Example
 /// ```
 /// use devtimer::run_benchmark;
@@ -91,27 +124,18 @@ pub struct DevTime {}
 /// }
 /// ```
 ///
-pub fn run_benchmark(iters: usize, function: impl Fn(usize)) -> RunThroughReport {
+pub fn run_benchmark<R>(iters: usize, function: impl Fn(usize) -> R) -> RunThroughReport {
     let mut timer = DevTime::new_simple();
-    let mut res = Vec::with_capacity(iters);
+    let mut samples = Vec::with_capacity(iters);
     for i in 0..iters {
         println!("Running iter {} ...", i + 1);
         timer.start();
-        (function)(i);
+        black_box((function)(i));
         timer.stop();
-        res.push(timer.time_in_nanos().unwrap());
-    }
-    res.sort();
-    let realindex = res.len() - 1;
-    let fastest = res[0];
-    let slowest = res[realindex];
-    let sum: u128 = res.into_iter().sum();
-    let avg: u128 = sum / (iters as u128);
-    RunThroughReport {
-        fastest,
-        slowest,
-        avg,
+        samples.push(timer.time_in_nanos().unwrap() as f64);
+        timer.reset();
     }
+    RunThroughReport::new(samples)
 }
 
 impl DevTime {
@@ -125,268 +149,105 @@ impl DevTime {
     }
 }
 
-/// # Complex Timer
-/// A complex timer wraps around a map of timer names and their corresponding
-/// `SimpleTimer` instances.
-pub struct ComplexTimer {
-    /// Map of timers and the corresponding `SimpleTimer`
-    timers: HashMap<&'static str, SimpleTimer>,
+/// Benchmark an operation that processes a known number of bytes per
+/// iteration, such as parsing a buffer or encoding a row.
+///
+/// This behaves exactly like [`run_benchmark`], except the resulting
+/// report also carries `bytes_per_iter`, enabling
+/// [`RunThroughReport::get_throughput_mbps`] and the MB/s line in
+/// [`RunThroughReport::print_stats`] — the number that usually matters
+/// more than a raw ns/iter count for data-processing benchmarks.
+/// ## Example
+/// ```
+/// use devtimer::run_benchmark_bytes;
+/// fn main() {
+///     let data = vec![0u8; 4096];
+///     let bench_result = run_benchmark_bytes(10, data.len() as u64, |_| {
+///         data.iter().fold(0u8, |acc, &b| acc.wrapping_add(b))
+///     });
+///     bench_result.print_stats();
+/// }
+/// ```
+pub fn run_benchmark_bytes<R>(
+    iters: usize,
+    bytes_per_iter: u64,
+    function: impl Fn(usize) -> R,
+) -> RunThroughReport {
+    let mut report = run_benchmark(iters, function);
+    report.set_bytes(bytes_per_iter);
+    report
 }
 
-impl ComplexTimer {
-    /// Return a new `ComplexTimer` instance
-    pub fn new() -> Self {
-        ComplexTimer {
-            timers: HashMap::new(),
-        }
-    }
-    /// Create a new timer tag. If the timer tag already exists, then this
-    /// function returns an error.
-    pub fn create_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
-        if self.timers.contains_key(timer_name) {
-            Err("This timer already exists")
-        } else {
-            let _ = self.timers.insert(
-                timer_name,
-                SimpleTimer {
-                    start: None,
-                    stop: None,
-                },
-            );
-            Ok(())
-        }
-    }
-    /// Start a timer with tag `timer_name`. If this timer tag doesn't exist,
-    /// then it returns an error
-    pub fn start_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
-        match self.timers.get_mut(timer_name) {
-            None => return Err("This timer does not exist"),
-            Some(x) => {
-                x.start = Some(time::Instant::now());
-                Ok(())
-            }
-        }
-    }
-    /// Stop a timer with tag `timer_name`. If this timer tag doesn't exist,
-    /// then it returns an error
-    pub fn stop_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
-        match self.timers.get_mut(timer_name) {
-            None => return Err("This timer does not exist"),
-            Some(x) => {
-                x.stop = Some(time::Instant::now());
-                Ok(())
-            }
-        }
-    }
-    /// Get the time in seconds for a timer with tag `timer_name`
-    pub fn time_in_secs(&self, timer_name: &'static str) -> Option<u64> {
-        match self.timers.get(timer_name) {
-            Some(t) => match t.find_diff() {
-                Some(diff) => Some(diff.as_secs()),
-                None => None,
-            },
-            None => return None,
-        }
-    }
-
-    /// Get the time in milliseconds for a timer with tag `timer_name`
-    pub fn time_in_millis(&self, timer_name: &'static str) -> Option<u128> {
-        match self.timers.get(timer_name) {
-            Some(t) => match t.find_diff() {
-                Some(diff) => Some(diff.as_millis()),
-                None => None,
-            },
-            None => return None,
-        }
-    }
-
-    /// Get the time in microseconds for a timer with tag `timer_name`
-    pub fn time_in_micros(&self, timer_name: &'static str) -> Option<u128> {
-        match self.timers.get(timer_name) {
-            Some(t) => match t.find_diff() {
-                Some(diff) => Some(diff.as_micros()),
-                None => None,
-            },
-            None => return None,
-        }
-    }
+/// The minimum time a warm-up phase should run for before estimates are
+/// taken, letting caches and branch predictors settle.
+const AUTO_WARMUP_TARGET: Duration = Duration::from_millis(100);
+/// The minimum wall-clock time a single measured batch should take, so
+/// that the overhead of `Instant::now()` is amortized across the batch.
+const AUTO_BATCH_TARGET: Duration = Duration::from_millis(1);
+/// The wall-clock budget for the measuring phase as a whole.
+const AUTO_WALL_CLOCK_BUDGET: Duration = Duration::from_secs(1);
+/// The maximum number of samples to collect, regardless of how much of
+/// the wall-clock budget remains.
+const AUTO_SAMPLE_BUDGET: usize = 1000;
 
-    /// Get the time in nanoseconds for a timer with tag `timer_name`
-    pub fn time_in_nanos(&self, timer_name: &'static str) -> Option<u128> {
-        match self.timers.get(timer_name) {
-            Some(t) => match t.find_diff() {
-                Some(diff) => Some(diff.as_nanos()),
-                None => None,
-            },
-            None => return None,
-        }
-    }
-    /// Delete a timer with tag `timer_name`
-    pub fn delete_timer(&mut self, timer_name: &'static str) -> Result<(), &'static str> {
-        match self.timers.remove_entry(timer_name) {
-            Some(_) => return Ok(()),
-            None => return Err("This timer does not exist"),
-        }
-    }
+/// Benchmark a fast operation without having to guess an iteration count.
+///
+/// This mimics the approach taken by the standard test harness's
+/// benchmarks: a short warm-up phase runs the closure repeatedly so
+/// caches and branch predictors settle, then the per-call cost is
+/// estimated from a single timed call and used to pick a batch size `n`
+/// large enough that one batch runs for at least a millisecond,
+/// amortizing the cost of `Instant::now()` itself. Batches are measured
+/// as a whole and divided by `n` to produce one ns/iter sample per
+/// batch; a batch that finishes too quickly to measure reliably grows
+/// the next batch's size by 1.1x, same as `libtest` does. Sampling
+/// continues until either a sample budget or a ~1 second wall-clock
+/// budget is exhausted.
+///
+/// As with [`run_benchmark`], the closure's return value is passed through
+/// [`black_box`] on every call.
+/// ## Example
+/// ```
+/// use devtimer::run_benchmark_auto;
+/// fn main() {
+///     let bench_result = run_benchmark_auto(|_| {
+///         let _ = 2 + 2;
+///     });
+///     bench_result.print_stats();
+/// }
+/// ```
+pub fn run_benchmark_auto<R>(function: impl Fn(usize) -> R) -> RunThroughReport {
+    let mut counter = 0usize;
 
-    /// Delete all set timers
-    pub fn clear_timers(&mut self) {
-        self.timers.clear();
+    let warmup_start = Instant::now();
+    while warmup_start.elapsed() < AUTO_WARMUP_TARGET {
+        black_box((function)(counter));
+        counter += 1;
     }
 
-    /// Print all results in the following format:
-    ///
-    /// ```log
-    /// timerx - 120 ns
-    /// timery - 1233 ns
-    /// ...
-    /// ```
-    pub fn print_results(&self) {
-        println!("");
-        for (k, v) in self.timers.iter() {
-            println!("{} - {} ns", k, v.time_in_nanos().unwrap());
-        }
-    }
-    /// Returns an iterator of timer tags and the corresponding `SimpleTimer` instances
-    /// # Example
-    /// ```
-    /// use devtimer::DevTime;
-    /// fn main() {
-    ///     let mut dt = DevTime::new_complex();
-    ///     for (name, timer) in dt.iter() {
-    ///         println!("Timer: {} took {} ns", name, timer.time_in_nanos().unwrap());
-    ///     }
-    /// }
-    /// ```
-    pub fn iter(&self) -> std::collections::hash_map::Iter<&'static str, SimpleTimer> {
-        self.timers.iter()
-    }
-}
+    let probe_start = Instant::now();
+    black_box((function)(counter));
+    counter += 1;
+    let per_call_ns = probe_start.elapsed().as_nanos().max(1);
+    let mut batch_size = ((AUTO_BATCH_TARGET.as_nanos() / per_call_ns) + 1) as usize;
 
-/// The `SimpleTimer` struct holds the start and stop time instances
-pub struct SimpleTimer {
-    start: Option<time::Instant>,
-    stop: Option<time::Instant>,
-}
-impl SimpleTimer {
-    /// Returns a new instance of the `DevTime` struct
-    pub fn new() -> Self {
-        SimpleTimer {
-            start: None,
-            stop: None,
+    let mut samples = Vec::new();
+    let measuring_start = Instant::now();
+    while samples.len() < AUTO_SAMPLE_BUDGET && measuring_start.elapsed() < AUTO_WALL_CLOCK_BUDGET
+    {
+        let batch_start = Instant::now();
+        for _ in 0..batch_size {
+            black_box((function)(counter));
+            counter += 1;
         }
-    }
-    /// Starts a timer on a mutable `DevTime` object
-    pub fn start(&mut self) {
-        self.start = Some(time::Instant::now());
-    }
-    /// Stops a timer on a mutable `DevTime` object
-    pub fn stop(&mut self) {
-        self.stop = Some(time::Instant::now());
-    }
-    /// Starts a timer after a specified duration
-    /// ## Example
-    /// ```
-    /// use devtimer::DevTime;
-    /// use std::time::Duration;
-    /// fn main() {
-    ///     let mut timer = DevTime::new_simple();
-    ///     timer.start_after(&Duration::from_secs(2));
-    ///     // The timer will automatically start after two seconds
-    ///     // do_some_long_operation();
-    ///     timer.stop();
-    ///     println!("Time taken: {}", timer.time_in_secs().unwrap());
-    ///     // The timer can be reused normally again
-    ///     timer.start(); // this starts the timer instantly
-    ///     // do_another_long_operation();
-    ///     timer.stop();
-    ///     println!("Time taken: {}", timer.time_in_secs().unwrap());
-    /// }
-    /// ```
-    /// ### Important Note
-    /// This will try to be as precise as possible. However exact precision cannot be guranteed.
-    /// As tested on multiple platforms, there are variations in the range of 0 to 10 nanoseconds.
-    pub fn start_after(&mut self, dur: &std::time::Duration) {
-        std::thread::sleep(*dur);
-        self.start = Some(time::Instant::now());
-    }
-    fn find_diff(&self) -> Option<time::Duration> {
-        match self.start {
-            Some(start) => match self.stop {
-                Some(stop) => {
-                    return Some(stop.duration_since(start));
-                }
-                _ => None,
-            },
-            _ => None,
+        let batch_elapsed = batch_start.elapsed();
+        samples.push(batch_elapsed.as_nanos() as f64 / (batch_size as f64));
+        if batch_elapsed < AUTO_BATCH_TARGET {
+            batch_size = ((batch_size as f64) * 1.1).ceil() as usize;
         }
     }
-    /// Returns an `Option<u128>` with the difference from the
-    /// starting time that was created with `start()` and the stop time
-    /// that was created with `stop()`. If both the fields exist, then the time
-    /// difference is returned in nanoseconds, otherwise `None` is returned
-    pub fn time_in_nanos(&self) -> Option<u128> {
-        match self.find_diff() {
-            Some(duration) => return Some(duration.as_nanos()),
-            _ => None,
-        }
-    }
-    /// Returns an `Option<u128>` with the difference from the
-    /// starting time that was created with `start()` and the stop time
-    /// that was created with `stop()`. If both the fields exist, then the time
-    /// difference is returned in microseconds, otherwise `None` is returned
-    pub fn time_in_micros(&self) -> Option<u128> {
-        match self.find_diff() {
-            Some(duration) => return Some(duration.as_micros()),
-            _ => None,
-        }
-    }
-    /// Returns an `Option<u128>` with the difference from the
-    /// starting time that was created with `start()` and the stop time
-    /// that was created with `stop()`. If both the fields exist, then the time
-    /// difference is returned in milliseconds, otherwise `None` is returned
-    pub fn time_in_millis(&self) -> Option<u128> {
-        match self.find_diff() {
-            Some(duration) => return Some(duration.as_millis()),
-            _ => None,
-        }
-    }
-    /// Returns an `Option<u64>` with the difference from the
-    /// starting time that was created with `start()` and the stop time
-    /// that was created with `stop()`. If both the fields exist, then the time
-    /// difference is returned in seconds, otherwise `None` is returned
-    pub fn time_in_secs(&self) -> Option<u64> {
-        match self.find_diff() {
-            Some(duration) => return Some(duration.as_secs()),
-            _ => None,
-        }
-    }
-}
-/// The `RunThroughReport` struct provides a benchmark report when calling
-/// `DevTime::run_benchmark()`.
-/// You can get the slowest, fastest and the average time taken per iteration
-/// by the `get_slowest()`, `get_fastest()` and `get_average()` functions
-/// respectively.
-pub struct RunThroughReport {
-    fastest: u128,
-    slowest: u128,
-    avg: u128,
-}
-impl RunThroughReport {
-    pub fn print_stats(&self) {
-        println!("\nSlowest: {} ns", self.slowest);
-        println!("Fastest: {} ns", self.fastest);
-        println!("Average: {} ns/iter", self.avg);
-    }
-    pub fn get_fastest(&self) -> u128 {
-        self.fastest
-    }
-    pub fn get_slowest(&self) -> u128 {
-        self.slowest
-    }
-    pub fn get_average(&self) -> u128 {
-        self.avg
-    }
+
+    RunThroughReport::new(samples)
 }
 
 #[test]
@@ -430,17 +291,137 @@ fn test_benchmark_impl() {
     use run_benchmark;
     let bench1 = run_benchmark(10, |_| {
         // Simulate a fake slow operation
-        std::thread::sleep(time::Duration::from_secs(1));
+        std::thread::sleep(std::time::Duration::from_secs(1));
     });
     // Print the results
     bench1.print_stats();
+    assert!(bench1.get_median() > 0.0);
+    assert!(bench1.get_std_dev() >= 0.0);
+}
+
+#[test]
+fn test_benchmark_bytes_impl() {
+    let bench1 = run_benchmark_bytes(10, 1024, |_| {
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    });
+    bench1.print_stats();
+    assert!(bench1.get_throughput_mbps().unwrap() > 0.0);
+}
+
+#[test]
+fn test_black_box_impl() {
+    assert_eq!(black_box(41) + 1, 42);
+}
+
+#[test]
+fn test_benchmark_auto_impl() {
+    let bench1 = run_benchmark_auto(|_| {
+        let _ = 2 + 2;
+    });
+    bench1.print_stats();
+    assert!(bench1.get_average() > 0.0);
 }
 
 #[test]
 fn test_simple_timer_impl() {
     let mut dt = DevTime::new_simple();
     dt.start();
-    std::thread::sleep(time::Duration::from_secs(10));
+    std::thread::sleep(std::time::Duration::from_secs(10));
     dt.stop();
     println!("Operation took: {}", dt.time_in_micros().unwrap());
 }
+
+#[test]
+fn test_simple_timer_pause_resume() {
+    let mut dt = DevTime::new_simple();
+    dt.start();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    dt.pause();
+    // This sleep should not be counted towards the measured time
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    dt.resume();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    dt.stop();
+    // The counted time should be close to the two 50ms segments, not the
+    // ~300ms of wall-clock time that actually passed
+    assert!(dt.time_in_millis().unwrap() < 200);
+}
+
+#[test]
+fn test_span_impl() {
+    let (elapsed, ret) = span(|| {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        42
+    });
+    assert_eq!(ret, 42);
+    assert!(elapsed >= std::time::Duration::from_millis(10));
+}
+
+#[test]
+fn test_timer_runner_schedule_after() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let runner = TimerRunner::new();
+    let fired = std::sync::Arc::new(AtomicUsize::new(0));
+    let fired_in_callback = fired.clone();
+    let _handle = runner.schedule_after(std::time::Duration::from_millis(10), move || {
+        fired_in_callback.fetch_add(1, Ordering::SeqCst);
+    });
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_timer_runner_schedule_interval() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let runner = TimerRunner::new();
+    let fired = std::sync::Arc::new(AtomicUsize::new(0));
+    let fired_in_callback = fired.clone();
+    let handle = runner.schedule_interval(std::time::Duration::from_millis(10), move || {
+        fired_in_callback.fetch_add(1, Ordering::SeqCst);
+    });
+    std::thread::sleep(std::time::Duration::from_millis(55));
+    handle.cancel();
+    let fired_at_cancel = fired.load(Ordering::SeqCst);
+    assert!(fired_at_cancel >= 3);
+    std::thread::sleep(std::time::Duration::from_millis(55));
+    // No more callbacks should have fired after cancellation
+    assert_eq!(fired.load(Ordering::SeqCst), fired_at_cancel);
+}
+
+#[test]
+fn test_timer_runner_reset() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let runner = TimerRunner::new();
+    let fired = std::sync::Arc::new(AtomicUsize::new(0));
+    let fired_in_callback = fired.clone();
+    let handle = runner.schedule_after(std::time::Duration::from_millis(30), move || {
+        fired_in_callback.fetch_add(1, Ordering::SeqCst);
+    });
+    std::thread::sleep(std::time::Duration::from_millis(15));
+    // Restart the countdown before it fires
+    runner.reset(&handle);
+    std::thread::sleep(std::time::Duration::from_millis(15));
+    assert_eq!(fired.load(Ordering::SeqCst), 0);
+    std::thread::sleep(std::time::Duration::from_millis(25));
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_timer_runner_reset_after_cancel_is_noop() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let runner = TimerRunner::new();
+    let fired = std::sync::Arc::new(AtomicUsize::new(0));
+    let fired_in_callback = fired.clone();
+    let handle = runner.schedule_after(std::time::Duration::from_millis(20), move || {
+        fired_in_callback.fetch_add(1, Ordering::SeqCst);
+    });
+    handle.cancel();
+    runner.reset(&handle);
+    assert!(!handle.is_active());
+    std::thread::sleep(std::time::Duration::from_millis(60));
+    assert_eq!(fired.load(Ordering::SeqCst), 0);
+}
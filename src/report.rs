@@ -0,0 +1,213 @@
+//! Benchmark reporting: [`RunThroughReport`] turns a batch of per-iteration
+//! timings into a summary that is actually safe to draw conclusions from.
+
+/// A single iteration's classification relative to the overall sample
+/// distribution, derived from the inter-quartile range (IQR).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutlierClass {
+    Normal,
+    Mild,
+    Severe,
+}
+
+/// The `RunThroughReport` struct provides a benchmark report when calling
+/// [`crate::run_benchmark`] (or one of its variants).
+///
+/// Besides the slowest, fastest and average time taken per iteration
+/// (via [`RunThroughReport::get_slowest`], [`RunThroughReport::get_fastest`]
+/// and [`RunThroughReport::get_average`]), the report keeps every sample it
+/// was built from, so it can also answer questions a single average can't:
+/// how spread out the samples are ([`RunThroughReport::get_std_dev`],
+/// [`RunThroughReport::percentile`]), how many of them look like noise
+/// rather than signal ([`RunThroughReport::outlier_counts`]), and, for
+/// benchmarks built with [`crate::run_benchmark_bytes`], the resulting
+/// throughput ([`RunThroughReport::get_throughput_mbps`]).
+pub struct RunThroughReport {
+    /// Every iteration's time in nanoseconds, sorted ascending. A plain
+    /// `f64` rather than an integer type, so that a batch average of a
+    /// sub-nanosecond-per-call closure (see [`crate::run_benchmark_auto`])
+    /// is carried through as a fraction instead of being floored to zero.
+    samples: Vec<f64>,
+    fastest: f64,
+    slowest: f64,
+    avg: f64,
+    /// Bytes processed per iteration, if the caller attached a size.
+    bytes_per_iter: Option<u64>,
+}
+
+impl RunThroughReport {
+    /// Build a report from a batch of per-iteration nanosecond timings.
+    ///
+    /// `samples` does not need to be sorted; it is sorted in place.
+    pub(crate) fn new(mut samples: Vec<f64>) -> Self {
+        assert!(!samples.is_empty(), "cannot report on zero samples");
+        samples.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        let fastest = samples[0];
+        let slowest = samples[samples.len() - 1];
+        let sum: f64 = samples.iter().sum();
+        let avg = sum / (samples.len() as f64);
+        Self {
+            samples,
+            fastest,
+            slowest,
+            avg,
+            bytes_per_iter: None,
+        }
+    }
+
+    /// Attach the number of bytes processed per iteration, enabling
+    /// [`RunThroughReport::get_throughput_mbps`] and the MB/s line in
+    /// [`RunThroughReport::print_stats`].
+    pub(crate) fn set_bytes(&mut self, bytes_per_iter: u64) {
+        self.bytes_per_iter = Some(bytes_per_iter);
+    }
+
+    /// The `p`-th percentile (0.0..=100.0) of the samples, using linear
+    /// interpolation between the two nearest ranks.
+    pub fn percentile(&self, p: f64) -> f64 {
+        assert!((0.0..=100.0).contains(&p), "percentile must be in 0..=100");
+        let n = self.samples.len();
+        if n == 1 {
+            return self.samples[0];
+        }
+        let rank = (p / 100.0) * ((n - 1) as f64);
+        let lo = rank.floor() as usize;
+        let hi = rank.ceil() as usize;
+        if lo == hi {
+            self.samples[lo]
+        } else {
+            let frac = rank - (lo as f64);
+            let (lo_val, hi_val) = (self.samples[lo], self.samples[hi]);
+            lo_val + (hi_val - lo_val) * frac
+        }
+    }
+
+    /// The median (50th percentile) of the samples, in nanoseconds.
+    pub fn get_median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// Q1 (25th percentile) and Q3 (75th percentile), in nanoseconds.
+    fn quartiles(&self) -> (f64, f64) {
+        (self.percentile(25.0), self.percentile(75.0))
+    }
+
+    /// The inter-quartile range `Q3 - Q1`, in nanoseconds.
+    pub fn get_iqr(&self) -> f64 {
+        let (q1, q3) = self.quartiles();
+        q3 - q1
+    }
+
+    /// The sample standard deviation (`sqrt(sum((x - mean)^2) / (n - 1))`)
+    /// of the samples, in nanoseconds. Returns `0.0` for a single sample.
+    pub fn get_std_dev(&self) -> f64 {
+        let n = self.samples.len();
+        if n < 2 {
+            return 0.0;
+        }
+        let mean = self.avg;
+        let sum_sq: f64 = self
+            .samples
+            .iter()
+            .map(|&x| {
+                let diff = x - mean;
+                diff * diff
+            })
+            .sum();
+        (sum_sq / ((n - 1) as f64)).sqrt()
+    }
+
+    /// The median absolute deviation (MAD) of the samples: the median of
+    /// `|x - median|` across all samples, in nanoseconds.
+    pub fn get_median_absolute_deviation(&self) -> f64 {
+        let median = self.get_median();
+        let mut deviations: Vec<f64> = self
+            .samples
+            .iter()
+            .map(|&x| (x - median).abs())
+            .collect();
+        deviations.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = deviations.len();
+        if n % 2 == 1 {
+            deviations[n / 2]
+        } else {
+            (deviations[n / 2 - 1] + deviations[n / 2]) / 2.0
+        }
+    }
+
+    fn classify(&self, sample: f64, q1: f64, q3: f64, iqr: f64) -> OutlierClass {
+        let severe_lo = q1 - 3.0 * iqr;
+        let severe_hi = q3 + 3.0 * iqr;
+        let mild_lo = q1 - 1.5 * iqr;
+        let mild_hi = q3 + 1.5 * iqr;
+        if sample < severe_lo || sample > severe_hi {
+            OutlierClass::Severe
+        } else if sample < mild_lo || sample > mild_hi {
+            OutlierClass::Mild
+        } else {
+            OutlierClass::Normal
+        }
+    }
+
+    /// The number of `(mild, severe)` outliers in the samples, classified
+    /// using the 1.5x/3x inter-quartile range rule.
+    pub fn outlier_counts(&self) -> (usize, usize) {
+        let (q1, q3) = self.quartiles();
+        let iqr = q3 - q1;
+        let (mut mild, mut severe) = (0, 0);
+        for &sample in &self.samples {
+            match self.classify(sample, q1, q3, iqr) {
+                OutlierClass::Mild => mild += 1,
+                OutlierClass::Severe => severe += 1,
+                OutlierClass::Normal => {}
+            }
+        }
+        (mild, severe)
+    }
+
+    /// The throughput in megabytes per second, if a per-iteration byte
+    /// count was attached (see [`crate::run_benchmark_bytes`]).
+    pub fn get_throughput_mbps(&self) -> Option<f64> {
+        self.bytes_per_iter
+            .map(|bytes| (bytes as f64) / (self.avg / 1e9) / 1e6)
+    }
+
+    pub fn print_stats(&self) {
+        println!("\nSlowest: {:.2} ns", self.slowest);
+        println!("Fastest: {:.2} ns", self.fastest);
+        println!("Average: {:.2} ns/iter", self.avg);
+        println!("Median: {:.2} ns", self.get_median());
+        println!("Std dev: {:.2} ns", self.get_std_dev());
+        println!("MAD: {:.2} ns", self.get_median_absolute_deviation());
+        let (q1, q3) = self.quartiles();
+        println!("IQR: {:.2} ns (Q1: {:.2}, Q3: {:.2})", q3 - q1, q1, q3);
+        let (mild, severe) = self.outlier_counts();
+        println!("{} outliers detected ({} mild, {} severe)", mild + severe, mild, severe);
+        if let Some(mbps) = self.get_throughput_mbps() {
+            println!("Throughput: {:.2} MB/s", mbps);
+        }
+    }
+    /// The fastest iteration's time, in nanoseconds.
+    ///
+    /// Returns `f64`, not `u128`: `run_benchmark_auto`'s batched samples
+    /// can be sub-nanosecond per call, and an integer return type would
+    /// floor those down to `0`. This is a breaking change for callers
+    /// still matching on `u128`.
+    pub fn get_fastest(&self) -> f64 {
+        self.fastest
+    }
+    /// The slowest iteration's time, in nanoseconds.
+    ///
+    /// Returns `f64`, not `u128`, for the same reason as
+    /// [`RunThroughReport::get_fastest`].
+    pub fn get_slowest(&self) -> f64 {
+        self.slowest
+    }
+    /// The average iteration time, in nanoseconds.
+    ///
+    /// Returns `f64`, not `u128`, for the same reason as
+    /// [`RunThroughReport::get_fastest`].
+    pub fn get_average(&self) -> f64 {
+        self.avg
+    }
+}
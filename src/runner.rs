@@ -0,0 +1,261 @@
+//! A background worker that fires user callbacks after a delay or on a
+//! repeating interval, alongside devtimer's passive measurement timers.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+type Callback = Box<dyn FnMut() + Send>;
+
+struct Entry {
+    callback: Callback,
+    /// `Some(interval)` if this timer repeats; `None` for a one-shot
+    interval: Option<Duration>,
+    /// The delay (or interval) this timer was last scheduled with, used to
+    /// restart it from now on `reset`
+    delay: Duration,
+    active: Arc<AtomicBool>,
+}
+
+struct State {
+    /// Pending timers ordered by next fire time; the first entry is always
+    /// the next deadline
+    pending: BTreeMap<(Instant, u64), Entry>,
+    /// Maps a timer's id to its current key in `pending`, so a timer can be
+    /// relocated (on reset) without scanning the whole map
+    deadlines: HashMap<u64, Instant>,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+/// A handle to a timer registered with a [`TimerRunner`]. Dropping a
+/// [`TimerHandle`] does **not** cancel the timer; call
+/// [`TimerHandle::cancel`] explicitly.
+pub struct TimerHandle {
+    id: u64,
+    active: Arc<AtomicBool>,
+}
+
+impl TimerHandle {
+    /// Cancel this timer. This is race-free even if the callback is
+    /// already being dispatched: a callback that is mid-flight still runs
+    /// to completion, but a repeating timer will not be rescheduled, and a
+    /// timer that hasn't fired yet will be skipped by the worker.
+    pub fn cancel(&self) {
+        self.active.store(false, Ordering::SeqCst);
+    }
+    /// Returns `true` unless this timer has been cancelled.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+/// A background timer runner: spawns a single worker thread and lets
+/// callers register callbacks to fire after a delay ([`TimerRunner::schedule_after`])
+/// or on a repeating interval ([`TimerRunner::schedule_interval`]).
+///
+/// Pending timers are kept in a `BTreeMap` keyed by `(fire_instant, id)`,
+/// so the next deadline is always the first entry; the worker sleeps
+/// until that deadline (or until woken by a condvar when a nearer timer
+/// is added or the runner is dropped), then drains and invokes every
+/// callback whose deadline has passed, re-inserting repeating ones at
+/// `deadline + interval`.
+///
+/// ## Example
+/// ```
+/// use devtimer::TimerRunner;
+/// use std::time::Duration;
+/// use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+///
+/// let runner = TimerRunner::new();
+/// let fired = Arc::new(AtomicUsize::new(0));
+/// let fired_in_callback = fired.clone();
+/// let _handle = runner.schedule_after(Duration::from_millis(10), move || {
+///     fired_in_callback.fetch_add(1, Ordering::SeqCst);
+/// });
+/// std::thread::sleep(Duration::from_millis(100));
+/// assert_eq!(fired.load(Ordering::SeqCst), 1);
+/// ```
+pub struct TimerRunner {
+    shared: Arc<Shared>,
+    next_id: AtomicU64,
+    worker: Option<thread::JoinHandle<()>>,
+}
+
+impl Default for TimerRunner {
+    fn default() -> Self {
+        TimerRunner::new()
+    }
+}
+
+impl TimerRunner {
+    /// Spawn a new `TimerRunner` and its background worker thread.
+    pub fn new() -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                pending: BTreeMap::new(),
+                deadlines: HashMap::new(),
+                shutdown: false,
+            }),
+            condvar: Condvar::new(),
+        });
+        let worker_shared = shared.clone();
+        let worker = thread::spawn(move || worker_loop(worker_shared));
+        Self {
+            shared,
+            next_id: AtomicU64::new(0),
+            worker: Some(worker),
+        }
+    }
+
+    /// Schedule `callback` to run once, after `delay` has elapsed.
+    pub fn schedule_after(
+        &self,
+        delay: Duration,
+        callback: impl FnMut() + Send + 'static,
+    ) -> TimerHandle {
+        self.schedule(delay, None, callback)
+    }
+
+    /// Schedule `callback` to run every `interval`, starting after the
+    /// first `interval` has elapsed.
+    pub fn schedule_interval(
+        &self,
+        interval: Duration,
+        callback: impl FnMut() + Send + 'static,
+    ) -> TimerHandle {
+        self.schedule(interval, Some(interval), callback)
+    }
+
+    fn schedule(
+        &self,
+        delay: Duration,
+        interval: Option<Duration>,
+        callback: impl FnMut() + Send + 'static,
+    ) -> TimerHandle {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let active = Arc::new(AtomicBool::new(true));
+        let deadline = Instant::now() + delay;
+        let entry = Entry {
+            callback: Box::new(callback),
+            interval,
+            delay,
+            active: active.clone(),
+        };
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            // `Option::is_none_or` would read better here, but this crate
+            // otherwise sticks to long-stable std APIs.
+            #[allow(clippy::unnecessary_map_or)]
+            let wake_worker = state
+                .pending
+                .keys()
+                .next()
+                .map_or(true, |&(soonest, _)| deadline < soonest);
+            state.deadlines.insert(id, deadline);
+            state.pending.insert((deadline, id), entry);
+            if wake_worker {
+                self.shared.condvar.notify_one();
+            }
+        }
+        TimerHandle { id, active }
+    }
+
+    /// Restart `handle`'s timer from now, using the delay (or interval) it
+    /// was originally registered with. Has no effect if the timer was
+    /// cancelled or has already fired (and was a one-shot).
+    pub fn reset(&self, handle: &TimerHandle) {
+        let mut state = self.shared.state.lock().unwrap();
+        let Some(old_deadline) = state.deadlines.remove(&handle.id) else {
+            return;
+        };
+        let Some(entry) = state.pending.remove(&(old_deadline, handle.id)) else {
+            return;
+        };
+        if !entry.active.load(Ordering::SeqCst) {
+            // The timer was cancelled; drop it instead of resurrecting it.
+            return;
+        }
+        let new_deadline = Instant::now() + entry.delay;
+        #[allow(clippy::unnecessary_map_or)]
+        let wake_worker = state
+            .pending
+            .keys()
+            .next()
+            .map_or(true, |&(soonest, _)| new_deadline < soonest);
+        state.deadlines.insert(handle.id, new_deadline);
+        state.pending.insert((new_deadline, handle.id), entry);
+        if wake_worker {
+            self.shared.condvar.notify_one();
+        }
+    }
+}
+
+impl Drop for TimerRunner {
+    fn drop(&mut self) {
+        {
+            let mut state = self.shared.state.lock().unwrap();
+            state.shutdown = true;
+        }
+        self.shared.condvar.notify_one();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn worker_loop(shared: Arc<Shared>) {
+    let mut state = shared.state.lock().unwrap();
+    loop {
+        if state.shutdown {
+            return;
+        }
+        let now = Instant::now();
+        let mut fired = Vec::new();
+        while let Some(&(deadline, id)) = state.pending.keys().next() {
+            if deadline > now {
+                break;
+            }
+            let entry = state.pending.remove(&(deadline, id)).unwrap();
+            state.deadlines.remove(&id);
+            fired.push((id, deadline, entry));
+        }
+
+        if fired.is_empty() {
+            state = match state.pending.keys().next() {
+                Some(&(deadline, _)) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    shared.condvar.wait_timeout(state, timeout).unwrap().0
+                }
+                None => shared.condvar.wait(state).unwrap(),
+            };
+            continue;
+        }
+
+        drop(state);
+        for (id, fired_deadline, mut entry) in fired {
+            if entry.active.load(Ordering::SeqCst) {
+                (entry.callback)();
+            }
+            if entry.active.load(Ordering::SeqCst) {
+                if let Some(interval) = entry.interval {
+                    let mut guard = shared.state.lock().unwrap();
+                    let deadline = fired_deadline + interval;
+                    guard.deadlines.insert(id, deadline);
+                    guard.pending.insert((deadline, id), entry);
+                }
+            }
+        }
+        state = shared.state.lock().unwrap();
+    }
+}
@@ -1,12 +1,32 @@
 use {
     crate::traits::TimeDifference,
-    std::{thread, time::Instant},
+    std::{
+        thread,
+        time::{Duration, Instant},
+    },
 };
 
+/// The state of a [`SimpleTimer`]'s logical clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimerState {
+    /// Never started (or reset)
+    Idle,
+    /// Currently counting time against the active segment
+    Running,
+    /// Started, but the active segment has been paused
+    Paused,
+    /// Finalized; the timer's `accumulated` field holds the counted time
+    Stopped,
+}
+
 #[derive(Debug, PartialEq)]
 /// A [`SimpleTimer`] is a timer object that can be used for simple timing operations. The timer can
 /// be reused by running [`SimpleTimer::reset`].
 ///
+/// A [`SimpleTimer`] tracks a logical clock rather than plain wall-clock time: [`SimpleTimer::pause`]
+/// and [`SimpleTimer::resume`] let you carve out setup/teardown work from the middle of a run, and
+/// that paused time is never counted.
+///
 /// ## Example
 /// ```
 /// use devtimer::{SimpleTimer, TimeDifferenceExt};
@@ -21,17 +41,20 @@ use {
 /// timer.reset(); // reset and use again
 /// ```
 pub struct SimpleTimer {
-    start: Option<Instant>,
-    stop: Option<Instant>,
+    state: TimerState,
+    /// The start instant of the currently active segment, if any
+    segment_start: Option<Instant>,
+    /// The counted time accumulated across all completed segments
+    accumulated: Duration,
     name: Box<str>,
 }
 
 impl TimeDifference for SimpleTimer {
-    fn start(&self) -> Option<Instant> {
-        self.start
-    }
-    fn stop(&self) -> Option<Instant> {
-        self.stop
+    fn elapsed(&self) -> Option<Duration> {
+        match self.state {
+            TimerState::Stopped => Some(self.accumulated),
+            _ => None,
+        }
     }
 }
 
@@ -41,16 +64,12 @@ impl Default for SimpleTimer {
     }
 }
 
-#[inline(always)]
-fn now() -> Option<Instant> {
-    Some(Instant::now())
-}
-
 impl SimpleTimer {
     fn _new(name: String) -> Self {
         Self {
-            start: None,
-            stop: None,
+            state: TimerState::Idle,
+            segment_start: None,
+            accumulated: Duration::ZERO,
             name: name.into_boxed_str(),
         }
     }
@@ -70,7 +89,9 @@ impl SimpleTimer {
     }
     /// Resets the timer
     pub fn reset(&mut self) {
-        (self.start, self.stop) = (None, None);
+        self.state = TimerState::Idle;
+        self.segment_start = None;
+        self.accumulated = Duration::ZERO;
     }
 }
 
@@ -81,24 +102,131 @@ impl SimpleTimer {
     ///
     /// This function will panic if the timer was already started
     pub fn start(&mut self) {
-        let call_time = now();
+        let call_time = Instant::now();
         assert!(
-            self.start.is_none(),
+            self.state == TimerState::Idle,
             "Timer `{}` was already started",
             self.name
         );
-        self.start = call_time;
+        self.segment_start = Some(call_time);
+        self.state = TimerState::Running;
     }
     /// Start the [`SimpleTimer`]. This will return `true` if the timer was never started
     /// and false in other cases
     pub fn start_checked(&mut self) -> bool {
-        let call_time = now();
-        let not_started = self.start.is_none();
+        let call_time = Instant::now();
+        let not_started = self.state == TimerState::Idle;
         if not_started {
-            self.start = call_time;
+            self.segment_start = Some(call_time);
+            self.state = TimerState::Running;
         }
         not_started
     }
+    /// Sleep for `dur`, then start the timer.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if the timer was already started
+    /// ## Example
+    /// ```
+    /// use devtimer::{SimpleTimer, TimeDifferenceExt};
+    /// use std::time::Duration;
+    ///
+    /// let mut timer = SimpleTimer::new();
+    /// timer.start_after(&Duration::from_millis(10));
+    /// // do_some_long_operation();
+    /// timer.stop();
+    /// println!("{}", timer.time_in_nanos().unwrap());
+    /// ```
+    pub fn start_after(&mut self, dur: &Duration) {
+        thread::sleep(*dur);
+        let call_time = Instant::now();
+        assert!(
+            self.state == TimerState::Idle,
+            "Timer `{}` was already started",
+            self.name
+        );
+        self.segment_start = Some(call_time);
+        self.state = TimerState::Running;
+    }
+}
+
+impl SimpleTimer {
+    /// Pause the timer, excluding the time from this point until [`SimpleTimer::resume`]
+    /// is called from the measured total
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if the timer is not currently running
+    pub fn pause(&mut self) {
+        let call_time = Instant::now();
+        assert!(
+            self.state == TimerState::Running,
+            "Timer `{}` is not running",
+            self.name
+        );
+        self.accumulated += call_time - self.segment_start.take().unwrap();
+        self.state = TimerState::Paused;
+    }
+    /// Pause the [`SimpleTimer`]. This will return `true` if the timer was running
+    /// and false in other cases
+    pub fn pause_checked(&mut self) -> bool {
+        let call_time = Instant::now();
+        let was_running = self.state == TimerState::Running;
+        if was_running {
+            self.accumulated += call_time - self.segment_start.take().unwrap();
+            self.state = TimerState::Paused;
+        }
+        was_running
+    }
+    /// Resume a paused timer
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if the timer is not currently paused
+    pub fn resume(&mut self) {
+        let call_time = Instant::now();
+        assert!(
+            self.state == TimerState::Paused,
+            "Timer `{}` is not paused",
+            self.name
+        );
+        self.segment_start = Some(call_time);
+        self.state = TimerState::Running;
+    }
+    /// Resume the [`SimpleTimer`]. This will return `true` if the timer was paused
+    /// and false in other cases
+    pub fn resume_checked(&mut self) -> bool {
+        let call_time = Instant::now();
+        let was_paused = self.state == TimerState::Paused;
+        if was_paused {
+            self.segment_start = Some(call_time);
+            self.state = TimerState::Running;
+        }
+        was_paused
+    }
+}
+
+impl SimpleTimer {
+    /// Time a closure and return how long it took alongside its return
+    /// value, without the two-call `start()`/`stop()` dance.
+    ///
+    /// The returned `Duration` composes directly with arithmetic and
+    /// comparisons, instead of forcing callers through the unit-specific
+    /// `time_in_*` accessors.
+    /// ## Example
+    /// ```
+    /// use devtimer::SimpleTimer;
+    ///
+    /// let (elapsed, doubled) = SimpleTimer::span(|| 21 * 2);
+    /// assert_eq!(doubled, 42);
+    /// println!("took {:?}", elapsed);
+    /// ```
+    pub fn span<R>(f: impl FnOnce() -> R) -> (Duration, R) {
+        let start = Instant::now();
+        let ret = f();
+        (start.elapsed(), ret)
+    }
 }
 
 impl SimpleTimer {
@@ -106,24 +234,31 @@ impl SimpleTimer {
     ///
     /// ## Panics
     ///
-    /// This function will panic if the timer has alread been stopped
+    /// This function will panic if the timer has already been stopped, or if it was
+    /// never started
     pub fn stop(&mut self) {
-        let call_time = now();
+        let call_time = Instant::now();
         assert!(
-            self.stop.is_none(),
-            "Timer `{}` was already stopped",
+            self.state == TimerState::Running || self.state == TimerState::Paused,
+            "Timer `{}` was already stopped, or was never started",
             self.name
         );
-        self.stop = call_time;
+        if let Some(segment_start) = self.segment_start.take() {
+            self.accumulated += call_time - segment_start;
+        }
+        self.state = TimerState::Stopped;
     }
-    /// Stop the [`SimpleTimer`]. This will return `true` if the timer was never stopped
+    /// Stop the [`SimpleTimer`]. This will return `true` if the timer was running or paused
     /// and false in other cases
     pub fn stop_checked(&mut self) -> bool {
-        let call_time = now();
-        let not_stopped = self.stop.is_none();
-        if not_stopped {
-            self.stop = call_time;
+        let call_time = Instant::now();
+        let can_stop = self.state == TimerState::Running || self.state == TimerState::Paused;
+        if can_stop {
+            if let Some(segment_start) = self.segment_start.take() {
+                self.accumulated += call_time - segment_start;
+            }
+            self.state = TimerState::Stopped;
         }
-        not_stopped
+        can_stop
     }
 }
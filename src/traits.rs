@@ -1,41 +1,29 @@
-use std::time::Instant;
+use std::time::Duration;
 
 pub trait TimeDifference {
-    fn start(&self) -> Option<Instant>;
-    fn stop(&self) -> Option<Instant>;
+    /// The counted time difference, if the implementor has finished measuring
+    fn elapsed(&self) -> Option<Duration>;
 }
 
 pub trait TimeDifferenceExt: TimeDifference {
     #[inline(always)]
     fn time_in_nanos(&self) -> Option<u128> {
-        match (self.start(), self.stop()) {
-            (Some(start), Some(stop)) => Some(stop.duration_since(start).as_nanos()),
-            _ => None,
-        }
+        self.elapsed().map(|d| d.as_nanos())
     }
 
     #[inline(always)]
     fn time_in_micros(&self) -> Option<u128> {
-        match (self.start(), self.stop()) {
-            (Some(start), Some(stop)) => Some(stop.duration_since(start).as_micros()),
-            _ => None,
-        }
+        self.elapsed().map(|d| d.as_micros())
     }
 
     #[inline(always)]
     fn time_in_millis(&self) -> Option<u128> {
-        match (self.start(), self.stop()) {
-            (Some(start), Some(stop)) => Some(stop.duration_since(start).as_millis()),
-            _ => None,
-        }
+        self.elapsed().map(|d| d.as_millis())
     }
 
     #[inline(always)]
     fn time_in_secs(&self) -> Option<u64> {
-        match (self.start(), self.stop()) {
-            (Some(start), Some(stop)) => Some(stop.duration_since(start).as_secs()),
-            _ => None,
-        }
+        self.elapsed().map(|d| d.as_secs())
     }
 }
 